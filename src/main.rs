@@ -1,9 +1,12 @@
 use clap::Parser;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 use walkdir::WalkDir;
-use humansize::{format_size, DECIMAL};
+use rayon::prelude::*;
+use humansize::{format_size, BINARY, DECIMAL};
 use anyhow::{Result, Context};
 
 #[derive(Parser)]
@@ -30,6 +33,127 @@ struct Args {
     /// Minimum size to display (e.g., 1MB, 500KB, 2GB). Default is 0 (show all)
     #[arg(short = 'm', long, default_value = "0")]
     min_size: String,
+
+    /// Show a logarithmic size-distribution histogram instead of the top-N list
+    #[arg(long, alias = "histogram")]
+    distribution: bool,
+
+    /// Suggest the smallest directory to delete to reclaim at least this much space (e.g., 1GB, 500MB)
+    #[arg(long)]
+    free: Option<String>,
+
+    /// Find duplicate files by content hash and report reclaimable space
+    #[arg(long)]
+    duplicates: bool,
+
+    /// Render results as an indented tree following the directory hierarchy
+    #[arg(long)]
+    tree: bool,
+
+    /// Display sizes using powers of 1024 (KiB, MiB, GiB)
+    #[arg(short = '2', long, conflicts_with = "si")]
+    binary: bool,
+
+    /// Display sizes using powers of 1000 (KB, MB, GB) [default]
+    #[arg(short = '0', long)]
+    si: bool,
+
+    /// Force a single fixed display unit (b, kb, ki, mb, mi, gb, gi, tb, ti)
+    #[arg(long, value_name = "UNIT")]
+    unit: Option<String>,
+
+    /// Cap the number of threads used for parallel scanning (defaults to rayon's automatic choice)
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Exclude files matching this glob pattern (repeatable)
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Only include files matching this glob pattern (repeatable)
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Read exclude glob patterns from a file, one per line
+    #[arg(long)]
+    exclude_from: Option<String>,
+
+    /// Count every hard-linked copy of a file instead of counting its bytes once (naive totals)
+    #[arg(long)]
+    count_links: bool,
+}
+
+/// Which convention to use when formatting sizes for display.
+enum UnitMode {
+    Decimal,
+    Binary,
+    Fixed(FixedUnit),
+}
+
+#[derive(Clone, Copy)]
+enum FixedUnit {
+    B,
+    KB,
+    KiB,
+    MB,
+    MiB,
+    GB,
+    GiB,
+    TB,
+    TiB,
+}
+
+impl FixedUnit {
+    fn divisor(self) -> f64 {
+        match self {
+            FixedUnit::B => 1.0,
+            FixedUnit::KB => 1_000.0,
+            FixedUnit::KiB => 1_024.0,
+            FixedUnit::MB => 1_000_000.0,
+            FixedUnit::MiB => 1_024.0 * 1_024.0,
+            FixedUnit::GB => 1_000_000_000.0,
+            FixedUnit::GiB => 1_024.0 * 1_024.0 * 1_024.0,
+            FixedUnit::TB => 1_000_000_000_000.0,
+            FixedUnit::TiB => 1_024.0 * 1_024.0 * 1_024.0 * 1_024.0,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            FixedUnit::B => "B",
+            FixedUnit::KB => "KB",
+            FixedUnit::KiB => "KiB",
+            FixedUnit::MB => "MB",
+            FixedUnit::MiB => "MiB",
+            FixedUnit::GB => "GB",
+            FixedUnit::GiB => "GiB",
+            FixedUnit::TB => "TB",
+            FixedUnit::TiB => "TiB",
+        }
+    }
+}
+
+fn parse_fixed_unit(unit_str: &str) -> Result<FixedUnit> {
+    match unit_str.to_lowercase().as_str() {
+        "b" => Ok(FixedUnit::B),
+        "kb" => Ok(FixedUnit::KB),
+        "ki" => Ok(FixedUnit::KiB),
+        "mb" => Ok(FixedUnit::MB),
+        "mi" => Ok(FixedUnit::MiB),
+        "gb" => Ok(FixedUnit::GB),
+        "gi" => Ok(FixedUnit::GiB),
+        "tb" => Ok(FixedUnit::TB),
+        "ti" => Ok(FixedUnit::TiB),
+        other => Err(anyhow::anyhow!("Unknown unit: {}. Use b, kb, ki, mb, mi, gb, gi, tb, or ti", other)),
+    }
+}
+
+fn format_size_with(size: u64, unit_mode: &UnitMode) -> String {
+    match unit_mode {
+        UnitMode::Decimal => format_size(size, DECIMAL),
+        UnitMode::Binary => format_size(size, BINARY),
+        UnitMode::Fixed(unit) => format!("{:.2} {}", size as f64 / unit.divisor(), unit.label()),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +161,9 @@ struct Item {
     path: String,
     size: u64,
     is_directory: bool,
+    /// Whether this item's bytes should contribute to aggregate totals (false for
+    /// hard-linked duplicates beyond the first, unless `--count-links` is set).
+    counted: bool,
 }
 
 fn parse_size(size_str: &str) -> Result<u64> {
@@ -56,11 +183,15 @@ fn parse_size(size_str: &str) -> Result<u64> {
     
     let multiplier = match unit_part {
         "" | "B" => 1,
-        "KB" => 1_024,
-        "MB" => 1_024 * 1_024,
-        "GB" => 1_024 * 1_024 * 1_024,
-        "TB" => 1_024_u64.pow(4),
-        _ => return Err(anyhow::anyhow!("Unknown size unit: {}. Use B, KB, MB, GB, or TB", unit_part)),
+        "KB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        "TB" => 1_000_000_000_000,
+        "KIB" => 1_024,
+        "MIB" => 1_024 * 1_024,
+        "GIB" => 1_024 * 1_024 * 1_024,
+        "TIB" => 1_024_u64.pow(4),
+        _ => return Err(anyhow::anyhow!("Unknown size unit: {}. Use B, KB/KiB, MB/MiB, GB/GiB, or TB/TiB", unit_part)),
     };
     
     Ok((number * multiplier as f64) as u64)
@@ -70,7 +201,7 @@ fn main() -> Result<()> {
     let args = Args::parse();
 
     // Determine what to include based on flags
-    let (include_files, include_directories) = if args.dirs_only {
+    let (include_files, mut include_directories) = if args.dirs_only {
         (false, true)
     } else if args.files_only {
         (true, false)
@@ -78,10 +209,36 @@ fn main() -> Result<()> {
         (true, true)  // Default: show both files and directories
     };
 
+    // --tree needs directory nodes to hang files under, even with --files-only
+    if args.tree {
+        include_directories = true;
+    }
+
     // Parse minimum size
     let min_size_bytes = parse_size(&args.min_size)
         .context(format!("Failed to parse minimum size: {}", args.min_size))?;
 
+    let unit_mode = if let Some(unit) = &args.unit {
+        UnitMode::Fixed(parse_fixed_unit(unit)?)
+    } else if args.binary {
+        UnitMode::Binary
+    } else {
+        UnitMode::Decimal
+    };
+
+    let mut exclude_patterns = compile_patterns(&args.exclude)?;
+    if let Some(exclude_from) = &args.exclude_from {
+        let contents = fs::read_to_string(exclude_from)
+            .context(format!("Failed to read --exclude-from file: {}", exclude_from))?;
+        let lines: Vec<String> = contents.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        exclude_patterns.extend(compile_patterns(&lines)?);
+    }
+    let include_patterns = compile_patterns(&args.include)?;
+
     let path = Path::new(&args.path);
     if !path.exists() {
         eprintln!("Error: Path '{}' does not exist", args.path);
@@ -90,69 +247,239 @@ fn main() -> Result<()> {
 
     println!("Analyzing path: {}", path.display());
     if min_size_bytes > 0 {
-        println!("Minimum size filter: {}", format_size(min_size_bytes, DECIMAL));
+        println!("Minimum size filter: {}", format_size_with(min_size_bytes, &unit_mode));
     }
     println!("Scanning files and directories...\n");
 
-    let items = scan_directory(&args.path, include_files, include_directories, min_size_bytes)?;
-    
-    if items.is_empty() {
+    let scan = scan_directory(
+        &args.path,
+        include_files,
+        include_directories,
+        min_size_bytes,
+        args.binary,
+        args.jobs,
+        &include_patterns,
+        &exclude_patterns,
+        args.count_links,
+    )?;
+
+    if args.duplicates {
+        let groups = find_duplicates(&scan.all_files, args.count_links)?;
+        display_duplicates(&groups, &unit_mode);
+        return Ok(());
+    }
+
+    if args.distribution {
+        display_histogram(&scan.histogram, args.binary, &unit_mode);
+        return Ok(());
+    }
+
+    if let Some(free) = &args.free {
+        let free_target = parse_size(free)
+            .context(format!("Failed to parse --free target: {}", free))?;
+        display_free_advisor(&scan.dir_sizes, free_target, args.limit, &unit_mode);
+        return Ok(());
+    }
+
+    if scan.items.is_empty() {
         println!("No items found matching the criteria.");
         return Ok(());
     }
 
-    display_results(items, args.limit);
-    
+    if args.tree {
+        display_tree(&args.path, &scan.items, args.limit, &unit_mode);
+        return Ok(());
+    }
+
+    display_results(scan.items, args.limit, &unit_mode);
+
     Ok(())
 }
 
-fn scan_directory(path: &str, include_files: bool, include_directories: bool, min_size: u64) -> Result<Vec<Item>> {
+fn compile_patterns(patterns: &[String]) -> Result<Vec<glob::Pattern>> {
+    patterns.iter()
+        .map(|p| glob::Pattern::new(p).context(format!("Invalid glob pattern: {}", p)))
+        .collect()
+}
+
+fn path_excluded(path: &str, include_patterns: &[glob::Pattern], exclude_patterns: &[glob::Pattern]) -> bool {
+    if !include_patterns.is_empty() && !include_patterns.iter().any(|p| p.matches(path)) {
+        return true;
+    }
+    exclude_patterns.iter().any(|p| p.matches(path))
+}
+
+/// The (volume, file index) pair identifying a physical file: `(dev, ino)` on Unix,
+/// `(volume_serial_number, file_index)` on Windows. `None` on platforms where
+/// hard-link identity can't be determined this way, in which case every path is
+/// treated as its own physical file.
+type FileId = Option<(u64, u64)>;
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> FileId {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(windows)]
+fn file_id(metadata: &fs::Metadata) -> FileId {
+    use std::os::windows::fs::MetadataExt;
+    match (metadata.volume_serial_number(), metadata.file_index()) {
+        (Some(volume), Some(index)) => Some((volume as u64, index)),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_id(_metadata: &fs::Metadata) -> FileId {
+    None
+}
+
+/// Everything `scan_directory` discovers in one pass: the display items, the size
+/// histogram, per-directory aggregate sizes, and every file's (path, size) for
+/// duplicate detection.
+struct ScanResult {
+    items: Vec<Item>,
+    histogram: HashMap<i64, (u64, u64)>,
+    dir_sizes: HashMap<String, u64>,
+    all_files: Vec<(String, u64)>,
+}
+
+/// A single file's stat results gathered during the parallel stat pass: its path,
+/// byte size, and physical file identity (for hard-link dedup).
+struct FileStat {
+    path: String,
+    size: u64,
+    id: FileId,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn scan_directory(
+    path: &str,
+    include_files: bool,
+    include_directories: bool,
+    min_size: u64,
+    binary_buckets: bool,
+    jobs: Option<usize>,
+    include_patterns: &[glob::Pattern],
+    exclude_patterns: &[glob::Pattern],
+    count_links: bool,
+) -> Result<ScanResult> {
+    // Single walk of the tree; files are stat'd in parallel below instead of per-pass.
+    // Include/exclude globs only ever filter files — directories must stay so the
+    // hierarchy and ancestor aggregates remain intact.
+    let entries: Vec<_> = WalkDir::new(path).min_depth(1).into_iter().filter_map(|e| e.ok())
+        .filter(|entry| !entry.path().is_file() || !path_excluded(&entry.path().to_string_lossy(), include_patterns, exclude_patterns))
+        .collect();
+
+    // Build a capped thread pool once when --jobs is given, and run every parallel
+    // phase below through it so the cap holds end to end, not just for the stat pass.
+    let pool = match jobs {
+        Some(n) => Some(rayon::ThreadPoolBuilder::new().num_threads(n).build()
+            .context("Failed to build thread pool")?),
+        None => None,
+    };
+
+    let stat = || -> Result<Vec<FileStat>> {
+        entries
+            .par_iter()
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| -> Result<FileStat> {
+                let metadata = fs::metadata(entry.path())
+                    .context(format!("Failed to get metadata for {}", entry.path().display()))?;
+                Ok(FileStat {
+                    path: entry.path().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    id: file_id(&metadata),
+                })
+            })
+            .collect()
+    };
+
+    let file_stats = match &pool {
+        Some(pool) => pool.install(stat)?,
+        None => stat()?,
+    };
+
+    // Decide which physical files count toward aggregate totals: every file if
+    // `--count-links` is set, otherwise only the first path seen for each inode.
+    let mut seen_ids: HashSet<(u64, u64)> = HashSet::new();
     let mut items = Vec::new();
-    let mut dir_sizes: HashMap<String, u64> = HashMap::new();
+    let mut histogram: HashMap<i64, (u64, u64)> = HashMap::new();
+    let mut all_files: Vec<(String, u64)> = Vec::new();
+    let mut counted_file_sizes: Vec<(String, u64)> = Vec::new();
 
-    // First pass: collect all file sizes and build directory size map
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        let entry_path = entry.path();
-        
-        if entry_path.is_file() {
-            let size = fs::metadata(entry_path)
-                .context(format!("Failed to get metadata for {}", entry_path.display()))?
-                .len();
-            
-            // Add file size to all parent directories
-            let mut current_path = entry_path.parent();
-            while let Some(parent) = current_path {
-                let parent_str = parent.to_string_lossy().to_string();
-                *dir_sizes.entry(parent_str).or_insert(0) += size;
-                current_path = parent.parent();
-            }
+    for FileStat { path: file_path, size, id } in &file_stats {
+        let counted = count_links || match id {
+            Some(file_id) => seen_ids.insert(*file_id),
+            None => true,
+        };
 
-            // Add file to items if files are included and size meets minimum requirement
-            if include_files && size >= min_size {
-                items.push(Item {
-                    path: entry_path.to_string_lossy().to_string(),
-                    size,
-                    is_directory: false,
-                });
-            }
+        if counted {
+            let bucket = histogram.entry(bucket_index(*size, binary_buckets)).or_insert((0, 0));
+            bucket.0 += 1;
+            bucket.1 += size;
+            counted_file_sizes.push((file_path.clone(), *size));
+        }
+
+        all_files.push((file_path.clone(), *size));
+
+        // Add file to items if files are included and size meets minimum requirement
+        if include_files && *size >= min_size {
+            items.push(Item {
+                path: file_path.clone(),
+                size: *size,
+                is_directory: false,
+                counted,
+            });
         }
     }
 
-    // Second pass: add directories if requested
+    // Propagate each counted file's size to all ancestor directories, folding
+    // per-thread partials and merging them into the final aggregate. Stop at the
+    // scanned root itself so directories outside the scanned tree never appear.
+    let root = Path::new(path);
+    let fold_dir_sizes = || {
+        counted_file_sizes
+            .par_iter()
+            .fold(HashMap::new, |mut acc: HashMap<String, u64>, (file_path, size)| {
+                let mut current = Path::new(file_path).parent();
+                while let Some(parent) = current {
+                    *acc.entry(parent.to_string_lossy().to_string()).or_insert(0) += size;
+                    if parent == root {
+                        break;
+                    }
+                    current = parent.parent();
+                }
+                acc
+            })
+            .reduce(HashMap::new, |mut a, b| {
+                for (dir, size) in b {
+                    *a.entry(dir).or_insert(0) += size;
+                }
+                a
+            })
+    };
+
+    let dir_sizes: HashMap<String, u64> = match &pool {
+        Some(pool) => pool.install(fold_dir_sizes),
+        None => fold_dir_sizes(),
+    };
+
+    // Add directories if requested
     if include_directories {
-        for entry in WalkDir::new(path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
-            let entry_path = entry.path();
-            
-            if entry_path.is_dir() {
-                let path_str = entry_path.to_string_lossy().to_string();
+        for entry in &entries {
+            if entry.path().is_dir() {
+                let path_str = entry.path().to_string_lossy().to_string();
                 let size = dir_sizes.get(&path_str).copied().unwrap_or(0);
-                
+
                 // Add directory to items only if size meets minimum requirement
                 if size >= min_size {
                     items.push(Item {
                         path: path_str,
                         size,
                         is_directory: true,
+                        counted: true,
                     });
                 }
             }
@@ -161,19 +488,259 @@ fn scan_directory(path: &str, include_files: bool, include_directories: bool, mi
 
     // Sort by size (largest first)
     items.sort_by(|a, b| b.size.cmp(&a.size));
-    
-    Ok(items)
+
+    Ok(ScanResult { items, histogram, dir_sizes, all_files })
+}
+
+/// A group of files sharing the same size and content hash.
+struct DuplicateGroup {
+    size: u64,
+    paths: Vec<String>,
+    /// Number of distinct physical files among `paths` (hard-linked copies of the
+    /// same inode only count once, unless `--count-links` is set).
+    distinct_copies: usize,
+}
+
+impl DuplicateGroup {
+    fn reclaimable(&self) -> u64 {
+        self.size * (self.distinct_copies.saturating_sub(1) as u64)
+    }
+}
+
+fn hash_file(path: &str) -> Result<([u8; 32], FileId)> {
+    let mut file = File::open(path).context(format!("Failed to open {}", path))?;
+    let id = file_id(&file.metadata().context(format!("Failed to get metadata for {}", path))?);
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buf).context(format!("Failed to read {}", path))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    Ok((*hasher.finalize().as_bytes(), id))
+}
+
+/// Files sharing the same (size, content hash), keyed by that pair, each entry
+/// holding the path and physical file identity of every member.
+type FileHashGroups = HashMap<(u64, [u8; 32]), Vec<(String, FileId)>>;
+
+fn find_duplicates(all_files: &[(String, u64)], count_links: bool) -> Result<Vec<DuplicateGroup>> {
+    // Only files whose size collides with at least one other file can be duplicates.
+    let mut by_size: HashMap<u64, Vec<&String>> = HashMap::new();
+    for (path, size) in all_files {
+        by_size.entry(*size).or_default().push(path);
+    }
+
+    let mut by_hash: FileHashGroups = HashMap::new();
+    for (size, paths) in by_size {
+        if paths.len() < 2 {
+            continue;
+        }
+
+        for path in paths {
+            let (hash, id) = hash_file(path)?;
+            by_hash.entry((size, hash)).or_default().push((path.clone(), id));
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_hash.into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|((size, _), members)| {
+            let mut seen_ids: HashSet<(u64, u64)> = HashSet::new();
+            let distinct_copies = members.iter()
+                .filter(|(_, id)| count_links || match id {
+                    Some(file_id) => seen_ids.insert(*file_id),
+                    None => true,
+                })
+                .count();
+            let paths = members.into_iter().map(|(path, _)| path).collect();
+            DuplicateGroup { size, paths, distinct_copies }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| b.reclaimable().cmp(&a.reclaimable()));
+
+    Ok(groups)
+}
+
+fn display_duplicates(groups: &[DuplicateGroup], unit_mode: &UnitMode) {
+    if groups.is_empty() {
+        println!("No duplicate files found.");
+        return;
+    }
+
+    let total_reclaimable: u64 = groups.iter().map(|g| g.reclaimable()).sum();
+    println!("Found {} duplicate set(s), {} reclaimable:\n", groups.len(), format_size_with(total_reclaimable, unit_mode));
+
+    for (index, group) in groups.iter().enumerate() {
+        println!("{}. {} each, {} reclaimable ({} paths, {} distinct copies):",
+                 index + 1,
+                 format_size_with(group.size, unit_mode),
+                 format_size_with(group.reclaimable(), unit_mode),
+                 group.paths.len(),
+                 group.distinct_copies);
+        for path in &group.paths {
+            println!("   {}", path);
+        }
+        println!();
+    }
+}
+
+/// Maps a file size to its logarithmic bucket index: base-10 normally, base-2 when `binary` is set.
+/// Bucket 0 is reserved for empty files; bucket `b >= 1` covers `[base^(b-1), base^b - 1]` bytes.
+fn bucket_index(size: u64, binary: bool) -> i64 {
+    if size == 0 {
+        0
+    } else if binary {
+        size.ilog2() as i64 + 1
+    } else {
+        size.ilog10() as i64 + 1
+    }
+}
+
+/// Formats `size` like `format_size_with`, but floors instead of rounds so a bucket's
+/// upper bound (e.g. 9999 bytes) never displays as the next bucket's lower bound (e.g.
+/// "10.00 kB" colliding with "10 kB").
+fn format_size_floor(size: u64, unit_mode: &UnitMode) -> String {
+    const DECIMAL_SCALE: [&str; 9] = ["B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
+    const BINARY_SCALE: [&str; 9] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB"];
+
+    let (divisor, scale) = match unit_mode {
+        UnitMode::Fixed(unit) => {
+            let floored = (size as f64 / unit.divisor() * 100.0).floor() / 100.0;
+            return format!("{:.2} {}", floored, unit.label());
+        }
+        UnitMode::Decimal => (1000.0, DECIMAL_SCALE),
+        UnitMode::Binary => (1024.0, BINARY_SCALE),
+    };
+
+    let mut value = size as f64;
+    let mut idx = 0;
+    while value >= divisor && idx < scale.len() - 1 {
+        value /= divisor;
+        idx += 1;
+    }
+    let floored = (value * 100.0).floor() / 100.0;
+    if idx == 0 {
+        format!("{} {}", floored as u64, scale[idx])
+    } else {
+        format!("{:.2} {}", floored, scale[idx])
+    }
+}
+
+fn bucket_range_label(bucket: i64, binary: bool, unit_mode: &UnitMode) -> String {
+    if bucket == 0 {
+        return format_size_with(0, unit_mode);
+    }
+
+    let (low, high) = if binary {
+        (1u64 << (bucket - 1) as u32, (1u64 << bucket as u32) - 1)
+    } else {
+        (10u64.pow((bucket - 1) as u32), 10u64.pow(bucket as u32) - 1)
+    };
+    format!("{}–{}", format_size_with(low, unit_mode), format_size_floor(high, unit_mode))
+}
+
+fn display_histogram(histogram: &HashMap<i64, (u64, u64)>, binary: bool, unit_mode: &UnitMode) {
+    println!("Size distribution:");
+    println!("{:<20} {:>10} {:>12}", "Range", "Count", "Total");
+    println!("{}", "-".repeat(45));
+
+    let mut buckets: Vec<(&i64, &(u64, u64))> = histogram.iter().collect();
+    buckets.sort_by_key(|(bucket, _)| **bucket);
+
+    let mut total_files = 0u64;
+    let mut total_bytes = 0u64;
+
+    for (bucket, (count, bytes)) in buckets {
+        println!("{:<20} {:>10} {:>12}", bucket_range_label(*bucket, binary, unit_mode), count, format_size_with(*bytes, unit_mode));
+        total_files += count;
+        total_bytes += bytes;
+    }
+
+    println!("\nTotal files: {}, total size: {}", total_files, format_size_with(total_bytes, unit_mode));
 }
 
-fn display_results(items: Vec<Item>, limit: usize) {
+fn display_free_advisor(dir_sizes: &HashMap<String, u64>, free_target: u64, limit: usize, unit_mode: &UnitMode) {
+    let mut candidates: Vec<(&String, &u64)> = dir_sizes.iter()
+        .filter(|(_, &size)| size >= free_target)
+        .collect();
+    candidates.sort_by_key(|(_, &size)| size);
+
+    if candidates.is_empty() {
+        println!("No single directory is large enough to reclaim {}.", format_size_with(free_target, unit_mode));
+        println!("Largest directories found:\n");
+
+        let mut largest: Vec<(&String, &u64)> = dir_sizes.iter().collect();
+        largest.sort_by(|a, b| b.1.cmp(a.1));
+
+        for (path, size) in largest.iter().take(limit) {
+            println!("{:<12} {}", format_size_with(**size, unit_mode), path);
+        }
+        return;
+    }
+
+    println!("Smallest directory that reclaims at least {}:\n", format_size_with(free_target, unit_mode));
+
+    for (path, size) in candidates.iter().take(limit) {
+        println!("{:<12} {}", format_size_with(**size, unit_mode), path);
+    }
+}
+
+fn display_tree(root: &str, items: &[Item], limit: usize, unit_mode: &UnitMode) {
+    let mut children: HashMap<String, Vec<&Item>> = HashMap::new();
+    for item in items {
+        if let Some(parent) = Path::new(&item.path).parent() {
+            children.entry(parent.to_string_lossy().to_string()).or_default().push(item);
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by(|a, b| b.size.cmp(&a.size));
+    }
+
+    println!("{}", root);
+    print_tree_node(root, &children, limit, "", unit_mode);
+}
+
+fn print_tree_node(path: &str, children: &HashMap<String, Vec<&Item>>, limit: usize, prefix: &str, unit_mode: &UnitMode) {
+    let Some(kids) = children.get(path) else { return };
+
+    let shown = std::cmp::min(kids.len(), limit);
+    let has_more = kids.len() > limit;
+
+    for (index, item) in kids.iter().take(shown).enumerate() {
+        let is_last = index == shown - 1 && !has_more;
+        let branch = if is_last { "└── " } else { "├── " };
+        let connector = if is_last { "    " } else { "│   " };
+        let name = Path::new(&item.path).file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| item.path.clone());
+
+        println!("{}{}{} ({})", prefix, branch, name, format_size_with(item.size, unit_mode));
+
+        if item.is_directory {
+            print_tree_node(&item.path, children, limit, &format!("{}{}", prefix, connector), unit_mode);
+        }
+    }
+
+    if has_more {
+        println!("{}└── ... and {} more", prefix, kids.len() - shown);
+    }
+}
+
+fn display_results(items: Vec<Item>, limit: usize, unit_mode: &UnitMode) {
     let display_count = std::cmp::min(items.len(), limit);
-    
+
     println!("Top {} largest items:", display_count);
     println!("{:<50} {:>12} {}", "Path", "Size", "Type");
     println!("{}", "-".repeat(70));
 
     for (index, item) in items.iter().take(limit).enumerate() {
-        let size_str = format_size(item.size, DECIMAL);
+        let size_str = format_size_with(item.size, unit_mode);
         let type_str = if item.is_directory { "DIR" } else { "FILE" };
         let path_display = if item.path.len() > 47 {
             format!("...{}", &item.path[item.path.len() - 44..])
@@ -192,10 +759,88 @@ fn display_results(items: Vec<Item>, limit: usize) {
         println!("\n... and {} more items", items.len() - limit);
     }
 
-    // Calculate total size based only on files to avoid double-counting
+    // Calculate total size based only on counted files to avoid double-counting
+    // directories and hard-linked duplicates
     let total_size: u64 = items.iter()
-        .filter(|item| !item.is_directory)
+        .filter(|item| !item.is_directory && item.counted)
         .map(|item| item.size)
         .sum();
-    println!("\nTotal size analyzed: {}", format_size(total_size, DECIMAL));
+    println!("\nTotal size analyzed: {}", format_size_with(total_size, unit_mode));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_index_zero_for_empty_files() {
+        assert_eq!(bucket_index(0, false), 0);
+        assert_eq!(bucket_index(0, true), 0);
+    }
+
+    #[test]
+    fn bucket_index_decimal_boundaries() {
+        assert_eq!(bucket_index(1, false), 1);
+        assert_eq!(bucket_index(9, false), 1);
+        assert_eq!(bucket_index(10, false), 2);
+        assert_eq!(bucket_index(99, false), 2);
+        assert_eq!(bucket_index(100, false), 3);
+        assert_eq!(bucket_index(999, false), 3);
+        assert_eq!(bucket_index(1000, false), 4);
+    }
+
+    #[test]
+    fn bucket_index_binary_boundaries() {
+        assert_eq!(bucket_index(1, true), 1);
+        assert_eq!(bucket_index(1023, true), 10);
+        assert_eq!(bucket_index(1024, true), 11);
+    }
+
+    #[test]
+    fn bucket_range_label_bounds_never_collide_across_buckets() {
+        let unit_mode = UnitMode::Decimal;
+        let lower_label = bucket_range_label(4, false, &unit_mode);
+        let upper_label = bucket_range_label(5, false, &unit_mode);
+        let lower_high = lower_label.split('–').nth(1).unwrap();
+        let upper_low = upper_label.split('–').next().unwrap();
+        assert_ne!(lower_high, upper_low);
+    }
+
+    /// Builds `root/sub/leaf.txt` under a fresh directory in the OS temp dir and
+    /// returns the root path; the caller is responsible for removing it.
+    fn make_scan_fixture(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("sizr-test-{}", name));
+        let sub = root.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(sub.join("leaf.txt"), b"0123456789").unwrap();
+        root
+    }
+
+    #[test]
+    fn scan_directory_dir_sizes_stop_at_scanned_root() {
+        let root = make_scan_fixture("ancestor-stop");
+        let scan = scan_directory(
+            root.to_str().unwrap(),
+            true,
+            true,
+            0,
+            false,
+            None,
+            &[],
+            &[],
+            false,
+        ).unwrap();
+
+        let root_str = root.to_string_lossy().to_string();
+        assert!(scan.dir_sizes.contains_key(&root_str));
+        for dir in scan.dir_sizes.keys() {
+            assert!(
+                Path::new(dir).starts_with(&root),
+                "dir_sizes should never surface an ancestor of the scanned root, got {}",
+                dir
+            );
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }